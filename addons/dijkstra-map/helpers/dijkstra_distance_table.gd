@@ -0,0 +1,52 @@
+# Precomputed path costs between chosen points, for queries that are asked
+# far more often than the graph changes, such as city-to-city distances
+# for strategy AI:
+#
+#   var table: DijkstraDistanceTable = DijkstraDistanceTable.new()
+#   table.compute(dijkstra_map, hubs, hubs)
+#   var cost: float = table.get_cost_between(hubs[0], hubs[1])
+#
+# The table does not follow later changes to the graph; compute it again
+# after editing the map.
+class_name DijkstraDistanceTable
+extends Reference
+
+# Target point ID -> {source point ID: cost}. Unreachable pairs are absent.
+var _costs: Dictionary = {}
+
+
+# Recalculates the map toward each of `targets` in turn and keeps the cost
+# from each of `sources` to it, or from every reached point if `sources`
+# is empty. This is one recalculation per target, and it overwrites the
+# map's results. `optional_params` is passed to every recalculation, with
+# `"input_is_destination"` forced to true.
+func compute(
+	dijkstra_map: DijkstraMap,
+	targets: PoolIntArray,
+	sources: PoolIntArray = PoolIntArray(),
+	optional_params: Dictionary = {}
+) -> void:
+	var params: Dictionary = optional_params.duplicate()
+	params["input_is_destination"] = true
+	_costs.clear()
+	for target in targets:
+		dijkstra_map.recalculate(target, params)
+		if sources.empty():
+			_costs[target] = dijkstra_map.get_cost_map()
+			continue
+		var target_costs: Dictionary = {}
+		var costs: PoolRealArray = dijkstra_map.get_cost_at_points(sources)
+		for i in range(sources.size()):
+			if not is_inf(costs[i]):
+				target_costs[sources[i]] = costs[i]
+		_costs[target] = target_costs
+
+
+# Returns the cost of the shortest path from `from` to `to`, INF if there
+# is none or if `to` was not one of the computed targets.
+func get_cost_between(from: int, to: int) -> float:
+	return _costs.get(to, {}).get(from, INF)
+
+
+func has_target(point_id: int) -> bool:
+	return _costs.has(point_id)
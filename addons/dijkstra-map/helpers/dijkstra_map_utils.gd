@@ -0,0 +1,223 @@
+# Static helpers built on the public DijkstraMap API. None of them keeps
+# state, so call them on the class:
+#
+#   DijkstraMapUtils.recalculate_with_initial_costs(dijkstra_map, {goal: 0.0, exit: 5.0})
+class_name DijkstraMapUtils
+extends Reference
+
+# Why `get_direction_info` found, or did not find, a next point.
+enum DirectionStatus { NEXT_POINT, ORIGIN, NO_PATH, DISABLED_POINT, MISSING_POINT }
+
+
+# Same as `DijkstraMap.recalculate`, with the origins given as a Dictionary
+# of point ID -> initial cost instead of parallel `origin` and
+# `"initial_costs"` arrays. `optional_params` must not contain
+# `"initial_costs"`.
+static func recalculate_with_initial_costs(
+	dijkstra_map: DijkstraMap, initial_costs: Dictionary, optional_params: Dictionary = {}
+) -> int:
+	if optional_params.has("initial_costs"):
+		push_error("initial_costs are already given as a Dictionary")
+		return FAILED
+	var origins: PoolIntArray = PoolIntArray()
+	var costs: Array = []
+	for point_id in initial_costs:
+		origins.push_back(point_id)
+		costs.push_back(initial_costs[point_id])
+	var params: Dictionary = optional_params.duplicate()
+	params["initial_costs"] = costs
+	return dijkstra_map.recalculate(origins, params)
+
+
+# Same as `DijkstraMap.get_shortest_path_from_point`, cut down to at most
+# `max_length` steps (no limit if negative). With `include_start`, the
+# path starts with `point_id` itself, unless there is no path at all.
+static func get_path_from_point(
+	dijkstra_map: DijkstraMap, point_id: int, max_length: int = -1, include_start: bool = false
+) -> PoolIntArray:
+	var path: PoolIntArray = dijkstra_map.get_shortest_path_from_point(point_id)
+	if max_length >= 0 and path.size() > max_length:
+		path.resize(max_length)
+	if include_start and dijkstra_map.get_direction_at_point(point_id) != -1:
+		path.insert(0, point_id)
+	return path
+
+
+# Returns the points of the shortest path from `point_id` together with
+# their costs, for markers such as "3 AP to here":
+#   "points": PoolIntArray, the path as returned by
+#       `get_shortest_path_from_point`;
+#   "segment_costs": PoolRealArray, the cost of each step;
+#   "cumulative_costs": PoolRealArray, the cost from `point_id` to each
+#       point.
+static func get_path_cost_breakdown_from_point(
+	dijkstra_map: DijkstraMap, point_id: int
+) -> Dictionary:
+	var points: PoolIntArray = dijkstra_map.get_shortest_path_from_point(point_id)
+	var segment_costs: PoolRealArray = PoolRealArray()
+	var cumulative_costs: PoolRealArray = PoolRealArray()
+	var start_cost: float = dijkstra_map.get_cost_at_point(point_id)
+	var previous_cost: float = start_cost
+	for cost in dijkstra_map.get_cost_at_points(points):
+		segment_costs.push_back(previous_cost - cost)
+		cumulative_costs.push_back(start_cost - cost)
+		previous_cost = cost
+	return {"points": points, "segment_costs": segment_costs, "cumulative_costs": cumulative_costs}
+
+
+# Same as `DijkstraMap.get_all_points_with_cost_between`, with the costs:
+#   "points": PoolIntArray, sorted by cost;
+#   "costs": PoolRealArray, the cost of each point.
+static func get_points_with_costs_between(
+	dijkstra_map: DijkstraMap, min_cost: float, max_cost: float
+) -> Dictionary:
+	var points: PoolIntArray = dijkstra_map.get_all_points_with_cost_between(min_cost, max_cost)
+	return {"points": points, "costs": dijkstra_map.get_cost_at_points(points)}
+
+
+# Returns 1 for each of `point_ids` that was reached by the last
+# recalculation (origins included) and 0 for the others.
+static func get_reachability_mask(
+	dijkstra_map: DijkstraMap, point_ids: PoolIntArray
+) -> PoolByteArray:
+	var mask: PoolByteArray = PoolByteArray()
+	for direction in dijkstra_map.get_direction_at_points(point_ids):
+		mask.push_back(0 if direction == -1 else 1)
+	return mask
+
+
+# Returns true if `point_id` is an origin of the last recalculation: the
+# direction of an origin is the origin itself. An origin whose
+# `"initial_costs"` entry made another origin cheaper does not count, as
+# its direction leads to that other origin.
+static func is_origin(dijkstra_map: DijkstraMap, point_id: int) -> bool:
+	return dijkstra_map.get_direction_at_point(point_id) == point_id
+
+
+# Returns every origin of the last recalculation, as defined by `is_origin`.
+static func get_origins(dijkstra_map: DijkstraMap) -> PoolIntArray:
+	var origins: PoolIntArray = PoolIntArray()
+	var directions: Dictionary = dijkstra_map.get_direction_map()
+	for point_id in directions:
+		if directions[point_id] == point_id:
+			origins.push_back(point_id)
+	return origins
+
+
+# Same as `DijkstraMap.get_direction_at_point`, but tells apart the cases
+# that it reports as `-1`. Returns `{"status": DirectionStatus,
+# "next_point": int}`, where `"next_point"` is `-1` unless the status is
+# `NEXT_POINT` or `ORIGIN`.
+static func get_direction_info(dijkstra_map: DijkstraMap, point_id: int) -> Dictionary:
+	var status: int = DirectionStatus.NEXT_POINT
+	var next_point: int = -1
+	if not dijkstra_map.has_point(point_id):
+		status = DirectionStatus.MISSING_POINT
+	elif dijkstra_map.is_point_disabled(point_id):
+		status = DirectionStatus.DISABLED_POINT
+	else:
+		next_point = dijkstra_map.get_direction_at_point(point_id)
+		if next_point == -1:
+			status = DirectionStatus.NO_PATH
+		elif next_point == point_id:
+			status = DirectionStatus.ORIGIN
+	return {"status": status, "next_point": next_point}
+
+
+# Returns the shortest route from `from` through each of `waypoints`, in
+# order, to `to`, as `{"path": PoolIntArray, "cost": float}`. The path
+# excludes `from`, like `get_shortest_path_from_point`. If a leg has no
+# path, the result is an empty path with a cost of INF.
+#
+# Each leg is one recalculation toward its end, so this overwrites the
+# map's results. `optional_params` is passed to every recalculation, with
+# `"input_is_destination"` forced to true.
+static func get_path_through_points(
+	dijkstra_map: DijkstraMap,
+	from: int,
+	waypoints: Array,
+	to: int,
+	optional_params: Dictionary = {}
+) -> Dictionary:
+	var params: Dictionary = optional_params.duplicate()
+	params["input_is_destination"] = true
+	var path: PoolIntArray = PoolIntArray()
+	var cost: float = 0.0
+	var leg_start: int = from
+	for leg_end in waypoints + [to]:
+		if leg_end == leg_start:
+			continue
+		dijkstra_map.recalculate(leg_end, params)
+		var leg_cost: float = dijkstra_map.get_cost_at_point(leg_start)
+		if is_inf(leg_cost):
+			return {"path": PoolIntArray(), "cost": INF}
+		path.append_array(dijkstra_map.get_shortest_path_from_point(leg_start))
+		cost += leg_cost
+		leg_start = leg_end
+	return {"path": path, "cost": cost}
+
+
+# Returns true if there is a path from `from` to `to`. The search starts
+# at `to` and stops as soon as it reaches `from`, so it is cheaper than a
+# full recalculation, but it still overwrites the map's results.
+# `optional_params` is passed to the recalculation, with
+# `"input_is_destination"` and `"termination_points"` set by this helper.
+static func has_path(
+	dijkstra_map: DijkstraMap, from: int, to: int, optional_params: Dictionary = {}
+) -> bool:
+	if not (dijkstra_map.has_point(from) and dijkstra_map.has_point(to)):
+		return false
+	var params: Dictionary = optional_params.duplicate()
+	params["input_is_destination"] = true
+	params["termination_points"] = from
+	dijkstra_map.recalculate(to, params)
+	return dijkstra_map.get_direction_at_point(from) != -1
+
+
+# Returns the reached point with the highest finite cost in the last
+# recalculation, `-1` if no point was reached.
+static func get_farthest_point(dijkstra_map: DijkstraMap) -> int:
+	var farthest_point: int = -1
+	var max_cost: float = -INF
+	var costs: Dictionary = dijkstra_map.get_cost_map()
+	for point_id in costs:
+		if costs[point_id] > max_cost:
+			max_cost = costs[point_id]
+			farthest_point = point_id
+	return farthest_point
+
+
+# Returns every reached point whose cost equals the highest finite cost
+# in the last recalculation.
+static func get_points_with_max_cost(dijkstra_map: DijkstraMap) -> PoolIntArray:
+	var points: PoolIntArray = PoolIntArray()
+	var farthest_point: int = get_farthest_point(dijkstra_map)
+	if farthest_point == -1:
+		return points
+	var max_cost: float = dijkstra_map.get_cost_at_point(farthest_point)
+	var costs: Dictionary = dijkstra_map.get_cost_map()
+	for point_id in costs:
+		if costs[point_id] == max_cost:
+			points.push_back(point_id)
+	return points
+
+
+# Checks the direction map of the last recalculation and returns the
+# points whose step is wrong: the next point was not reached, or its cost
+# is not strictly lower. Origins, which point to themselves, are skipped.
+# When nothing is returned, every path ends at an origin, because costs
+# strictly decrease along it. Connections of weight 0 are reported, as
+# they leave the cost unchanged.
+static func verify_field_consistency(dijkstra_map: DijkstraMap) -> PoolIntArray:
+	var offending_points: PoolIntArray = PoolIntArray()
+	var directions: Dictionary = dijkstra_map.get_direction_map()
+	var costs: Dictionary = dijkstra_map.get_cost_map()
+	for point_id in directions:
+		var next_point: int = directions[point_id]
+		if next_point == point_id:
+			continue
+		if not costs.has(next_point) or not costs.has(point_id):
+			offending_points.push_back(point_id)
+		elif costs[next_point] >= costs[point_id]:
+			offending_points.push_back(point_id)
+	return offending_points
@@ -0,0 +1,118 @@
+# Follows the direction field of a DijkstraMap, in the spirit of
+# NavigationAgent2D.
+#
+# Add it as a child of the node that moves. Every physics frame the agent
+# looks up the next point from `get_direction_at_point` and emits
+# `velocity_computed`; moving the parent is left to the user:
+#
+#   func _on_DijkstraAgent2D_velocity_computed(velocity: Vector2) -> void:
+#       move_and_slide(velocity)
+#
+# The map can be shared by many agents and recalculated elsewhere. Setting
+# `target_point` makes this agent recalculate the map toward that point
+# itself.
+class_name DijkstraAgent2D, "res://addons/dijkstra-map/dm_icon.png"
+extends Node2D
+
+signal velocity_computed(velocity)
+signal target_reached
+
+export var max_speed: float = 200.0
+# How close to a point the agent has to be before heading to the next one.
+export var point_reached_distance: float = 4.0
+
+var dijkstra_map: DijkstraMap setget set_dijkstra_map
+# Point ID -> global position, for every point the agent may walk through.
+# Points missing from it are treated as having no path.
+var point_positions: Dictionary = {}
+# Options passed to `recalculate` when `target_point` is set.
+var recalculate_options: Dictionary = {}
+# Point the agent is currently standing on, `-1` if unknown.
+var current_point: int = -1
+# Origin to recalculate the map toward, `-1` if the map is managed elsewhere.
+var target_point: int = -1 setget set_target_point
+var velocity: Vector2 = Vector2.ZERO
+
+var _target_reached: bool = false
+
+
+# Also recalculates toward `target_point` if it was set before the map.
+func set_dijkstra_map(map: DijkstraMap) -> void:
+	dijkstra_map = map
+	_target_reached = false
+	_recalculate_toward_target()
+
+
+func set_target_point(point_id: int) -> void:
+	target_point = point_id
+	_target_reached = false
+	_recalculate_toward_target()
+
+
+# Sets `current_point` to the point closest to the agent. This is linear in
+# the number of points, so call it once on spawn rather than every frame.
+func snap_to_nearest_point() -> void:
+	var best_distance: float = INF
+	for point_id in point_positions:
+		var distance: float = global_position.distance_squared_to(point_positions[point_id])
+		if distance < best_distance:
+			best_distance = distance
+			current_point = point_id
+	_target_reached = false
+
+
+# Returns the point the agent is heading to, `-1` if there is no path.
+func get_next_point() -> int:
+	if dijkstra_map == null or not point_positions.has(current_point):
+		return -1
+	var next_point: int = dijkstra_map.get_direction_at_point(current_point)
+	if not point_positions.has(next_point):
+		return -1
+	return next_point
+
+
+# Returns the remaining points toward the target, `current_point` excluded.
+func get_current_path() -> PoolIntArray:
+	if get_next_point() == -1:
+		return PoolIntArray()
+	return dijkstra_map.get_shortest_path_from_point(current_point)
+
+
+func is_target_reachable() -> bool:
+	return get_next_point() != -1
+
+
+func is_target_reached() -> bool:
+	return _target_reached
+
+
+func _physics_process(_delta: float) -> void:
+	var next_point: int = get_next_point()
+	if next_point != -1 and next_point != current_point:
+		if global_position.distance_to(point_positions[next_point]) <= point_reached_distance:
+			current_point = next_point
+			next_point = get_next_point()
+
+	velocity = Vector2.ZERO
+	if next_point != current_point:
+		# The map may have been recalculated elsewhere since the last arrival.
+		_target_reached = false
+
+	if next_point == -1:
+		pass
+	elif next_point == current_point:
+		# The direction of an origin is the origin itself.
+		var arrival: Vector2 = point_positions[current_point]
+		if global_position.distance_to(arrival) > point_reached_distance:
+			velocity = global_position.direction_to(arrival) * max_speed
+		elif not _target_reached:
+			_target_reached = true
+			emit_signal("target_reached")
+	else:
+		velocity = global_position.direction_to(point_positions[next_point]) * max_speed
+	emit_signal("velocity_computed", velocity)
+
+
+func _recalculate_toward_target() -> void:
+	if dijkstra_map != null and target_point >= 0:
+		dijkstra_map.recalculate(target_point, recalculate_options)
@@ -0,0 +1,179 @@
+# Draws the last recalculation of a square-grid DijkstraMap: a heat color per
+# cell, an arrow toward the next point, and optionally the cost as text.
+#
+# Give it the map and the Dictionary returned by `add_square_grid`, then call
+# `refresh()` after each recalculation:
+#
+#   overlay.dijkstra_map = dijkstra_map
+#   overlay.pos_to_id = dijkstra_map.add_square_grid(Rect2(0, 0, 23, 19))
+#   dijkstra_map.recalculate(origin)
+#   overlay.refresh()
+#
+# It runs in the editor too, so tool scripts can use it to preview maps.
+tool
+class_name DijkstraDebugOverlay, "res://addons/dijkstra-map/dm_icon.png"
+extends Node2D
+
+# Glyphs `dump_grid` uses for each step toward the next cell.
+const DIRECTION_GLYPHS: Dictionary = {
+	Vector2(1, 0): "→",
+	Vector2(1, 1): "↘",
+	Vector2(0, 1): "↓",
+	Vector2(-1, 1): "↙",
+	Vector2(-1, 0): "←",
+	Vector2(-1, -1): "↖",
+	Vector2(0, -1): "↑",
+	Vector2(1, -1): "↗"
+}
+
+export var cell_size: Vector2 = Vector2(32, 32)
+export var show_costs: bool = true setget set_show_costs
+export var show_directions: bool = true setget set_show_directions
+# Costs at or above this value get the hottest color.
+export var max_cost: float = 32.0 setget set_max_cost
+# Maps cost / max_cost to a color. If unset, `low_cost_color` and
+# `high_cost_color` are blended instead.
+export var gradient: Gradient setget set_gradient
+export var low_cost_color: Color = Color(0.1, 0.2, 0.9, 0.5)
+export var high_cost_color: Color = Color(0.9, 0.1, 0.1, 0.5)
+export var unreachable_color: Color = Color(0, 0, 0, 0.5)
+export var arrow_color: Color = Color(1, 1, 1, 0.8)
+# Cost numbers are only drawn when a font is set.
+export var font: Font setget set_font
+
+var dijkstra_map: DijkstraMap setget set_dijkstra_map
+# Cell coordinates (Vector2) -> point ID, as returned by `add_square_grid`.
+var pos_to_id: Dictionary = {} setget set_pos_to_id
+
+var _id_to_pos: Dictionary = {}
+
+
+func set_dijkstra_map(value: DijkstraMap) -> void:
+	dijkstra_map = value
+	update()
+
+
+func set_pos_to_id(value: Dictionary) -> void:
+	pos_to_id = value
+	_id_to_pos.clear()
+	for pos in pos_to_id:
+		_id_to_pos[pos_to_id[pos]] = pos
+	update()
+
+
+func set_show_costs(value: bool) -> void:
+	show_costs = value
+	update()
+
+
+func set_show_directions(value: bool) -> void:
+	show_directions = value
+	update()
+
+
+func set_max_cost(value: float) -> void:
+	max_cost = value
+	update()
+
+
+func set_gradient(value: Gradient) -> void:
+	gradient = value
+	update()
+
+
+func set_font(value: Font) -> void:
+	font = value
+	update()
+
+
+# Redraws the overlay from the map's current results.
+func refresh() -> void:
+	update()
+
+
+# Returns the costs of the cells in `rect` as text, one line per row, for
+# diffing in tests and bug reports. With `directions`, each cell shows an
+# arrow toward its next cell instead. Origins are shown as `*`, unreachable
+# cells as `.` and cells without a point as `#`.
+func dump_grid(rect: Rect2, directions: bool = false) -> String:
+	if dijkstra_map == null:
+		return ""
+	var rows: Array = []
+	var width: int = 1
+	for y in range(int(rect.position.y), int(rect.end.y)):
+		var row: Array = []
+		for x in range(int(rect.position.x), int(rect.end.x)):
+			var text: String = _dump_cell(Vector2(x, y), directions)
+			width = int(max(width, text.length()))
+			row.push_back(text)
+		rows.push_back(row)
+	var lines: PoolStringArray = PoolStringArray()
+	var cell_format: String = "%" + str(width) + "s"
+	for row in rows:
+		var cells: PoolStringArray = PoolStringArray()
+		for text in row:
+			cells.push_back(cell_format % text)
+		lines.push_back(cells.join(" "))
+	return lines.join("\n")
+
+
+func _draw() -> void:
+	if dijkstra_map == null:
+		return
+	if show_costs:
+		_draw_costs()
+	if show_directions:
+		_draw_directions()
+
+
+func _draw_costs() -> void:
+	var costs: Dictionary = dijkstra_map.get_cost_map()
+	for pos in pos_to_id:
+		var rect: Rect2 = Rect2(pos * cell_size, cell_size)
+		var id: int = pos_to_id[pos]
+		if not costs.has(id):
+			draw_rect(rect, unreachable_color)
+			continue
+		var cost: float = costs[id]
+		draw_rect(rect, _cost_color(cost))
+		if font != null:
+			var text: String = str(stepify(cost, 0.1))
+			var text_size: Vector2 = font.get_string_size(text)
+			# draw_string() takes the baseline, so shift down by the ascent.
+			var offset: Vector2 = Vector2(-text_size.x / 2, font.get_ascent() - text_size.y / 2)
+			draw_string(font, rect.position + cell_size / 2 + offset, text)
+
+
+func _draw_directions() -> void:
+	var directions: Dictionary = dijkstra_map.get_direction_map()
+	for id in directions:
+		var next_id: int = directions[id]
+		if next_id == id or not _id_to_pos.has(id) or not _id_to_pos.has(next_id):
+			continue
+		var center: Vector2 = (_id_to_pos[id] + Vector2(0.5, 0.5)) * cell_size
+		var tip: Vector2 = center + (_id_to_pos[next_id] - _id_to_pos[id]) * cell_size * 0.4
+		var head: Vector2 = (center - tip).normalized() * cell_size.x * 0.15
+		draw_line(center, tip, arrow_color, 2.0)
+		draw_line(tip, tip + head.rotated(PI / 6), arrow_color, 2.0)
+		draw_line(tip, tip + head.rotated(-PI / 6), arrow_color, 2.0)
+
+
+func _cost_color(cost: float) -> Color:
+	var ratio: float = clamp(cost / max_cost, 0.0, 1.0) if max_cost > 0.0 else 1.0
+	if gradient != null:
+		return gradient.interpolate(ratio)
+	return low_cost_color.linear_interpolate(high_cost_color, ratio)
+
+
+func _dump_cell(pos: Vector2, directions: bool) -> String:
+	if not pos_to_id.has(pos):
+		return "#"
+	var id: int = pos_to_id[pos]
+	var next_id: int = dijkstra_map.get_direction_at_point(id)
+	if next_id == -1:
+		return "."
+	if next_id == id:
+		return "*"
+	if directions:
+		return DIRECTION_GLYPHS.get(_id_to_pos.get(next_id, pos) - pos, "?")
+	return str(stepify(dijkstra_map.get_cost_at_point(id), 0.1))
@@ -0,0 +1,338 @@
+# Shared code of DijkstraTileMap2D and DijkstraGridMap3D: keeps a
+# DijkstraMap in sync with the used cells of a grid node and exposes
+# cell-addressed queries. It is not meant to be used on its own.
+#
+# Every used cell becomes a point. Its terrain comes from `item_terrains`,
+# and items that are not listed get terrain -1, which always has a weight
+# of 1.0. Subclasses decide which cells each cell links to.
+extends Node
+
+# Tile or item ID -> terrain ID. Unlisted IDs use terrain -1.
+export var item_terrains: Dictionary = {}
+# Orthogonal connections are disabled when this is INF or NAN.
+export var orthogonal_cost: float = 1.0
+# Diagonal connections are disabled when this is INF or NAN.
+export var diagonal_cost: float = INF
+# Step -> cost, overriding `orthogonal_cost` or `diagonal_cost` for moves
+# in that direction only, for wind or currents. Steps are Vector2 cells
+# for TileMaps and horizontal Vector3 cells for GridMaps. INF or NAN
+# disables moves in that direction.
+export var step_costs: Dictionary = {}
+# Defaults for the `recalculate` options of the same name. Options passed
+# to `recalculate` take precedence.
+export var input_is_destination: bool = true
+export var maximum_cost: float = INF
+export var terrain_weights: Dictionary = {}
+
+var dijkstra_map: DijkstraMap = DijkstraMap.new()
+
+var _grid: Node
+var _cell_to_id: Dictionary = {}
+var _id_to_cell: Dictionary = {}
+var _elevations: Dictionary = {}
+var _uphill_factor: float = 0.0
+var _downhill_factor: float = 0.0
+
+
+func _ready() -> void:
+	if not _get_grid_path().is_empty():
+		rebuild()
+
+
+# Rebuilds the whole graph from the grid node.
+func rebuild() -> void:
+	if not _resolve_grid():
+		return
+	dijkstra_map.clear()
+	_cell_to_id.clear()
+	_id_to_cell.clear()
+	var cells: Array = _grid.get_used_cells()
+	for cell in cells:
+		_add_cell(cell)
+	for cell in cells:
+		_connect_from(cell)
+
+
+# Brings a single cell in line with the grid node after it was edited.
+func update_cell(cell) -> void:
+	if not _resolve_grid():
+		return
+	if _cell_to_id.has(cell):
+		var id: int = _cell_to_id[cell]
+		dijkstra_map.remove_point(id)
+		_cell_to_id.erase(cell)
+		_id_to_cell.erase(id)
+	if _get_cell_item(cell) == -1:
+		return
+	_add_cell(cell)
+	_connect_from(cell)
+	for other_cell in _get_neighbours(cell):
+		if _cell_to_id.has(other_cell):
+			for link in _get_links(other_cell):
+				if link[0] == cell:
+					_connect_link(other_cell, link)
+
+
+# Adds a slope cost to every connection between cells listed in
+# `elevations` (cell -> height): `uphill_factor` times the height gained,
+# or `downhill_factor` times the height lost. A negative `downhill_factor`
+# makes descending cheaper, down to a weight of 0. The costs are kept
+# across `rebuild` and `update_cell`; pass an empty Dictionary to remove
+# them.
+func apply_slope_costs(
+	elevations: Dictionary, uphill_factor: float, downhill_factor: float
+) -> void:
+	_elevations = elevations
+	_uphill_factor = uphill_factor
+	_downhill_factor = downhill_factor
+	for cell in _cell_to_id:
+		for link in _get_links(cell):
+			if _cell_to_id.has(link[0]):
+				dijkstra_map.remove_connection(_cell_to_id[cell], _cell_to_id[link[0]], false)
+				_connect_link(cell, link)
+
+
+# Returns the point ID of `cell`, `-1` if the cell is empty.
+func get_id_for_cell(cell) -> int:
+	return _cell_to_id.get(cell, -1)
+
+
+# Returns the cell of `point_id`, or `null` if the point is unknown.
+func get_cell_for_id(point_id: int):
+	return _id_to_cell.get(point_id)
+
+
+# Same as `DijkstraMap.recalculate`, `origin` being point IDs. Options
+# missing from `optional_params` come from the exported defaults.
+func recalculate(origin, optional_params: Dictionary = {}) -> int:
+	return dijkstra_map.recalculate(origin, _with_defaults(optional_params))
+
+
+# Same as `recalculate`, leaving out for this call every cell for which
+# `include_cell.call_func(cell)` is false (a tile currently on fire, for
+# example). The excluded points are disabled during the recalculation and
+# enabled again afterwards. The FuncRef is called once per cell, so this
+# costs a GDScript call per cell on top of the recalculation.
+func recalculate_filtered(origin, include_cell: FuncRef, optional_params: Dictionary = {}) -> int:
+	var excluded: Array = []
+	for cell in _cell_to_id:
+		var id: int = _cell_to_id[cell]
+		if not dijkstra_map.is_point_disabled(id) and not include_cell.call_func(cell):
+			dijkstra_map.disable_point(id)
+			excluded.push_back(id)
+	var result: int = recalculate(origin, optional_params)
+	for id in excluded:
+		dijkstra_map.enable_point(id)
+	return result
+
+
+# Computes the cost between every pair of cells, for board-game-sized
+# maps that are queried far more often than they change. This is one
+# recalculation per cell, so it refuses maps with more than `max_points`
+# cells and returns null. It overwrites the map's results, and the table
+# does not follow later edits.
+func compute_all_pairs(
+	max_points: int = 1000, optional_params: Dictionary = {}
+) -> DijkstraDistanceTable:
+	if _cell_to_id.size() > max_points:
+		push_error("%s: %d cells is over max_points" % [name, _cell_to_id.size()])
+		return null
+	var table: DijkstraDistanceTable = DijkstraDistanceTable.new()
+	var points: PoolIntArray = PoolIntArray(_id_to_cell.keys())
+	table.compute(dijkstra_map, points, PoolIntArray(), _with_defaults(optional_params))
+	return table
+
+
+func get_cost_at_cell(cell) -> float:
+	return dijkstra_map.get_cost_at_point(get_id_for_cell(cell))
+
+
+# Same as `DijkstraMap.set_terrain_for_point`. `FAILED` if the cell is
+# empty. The terrain is reset by `rebuild` and `update_cell`.
+func set_terrain_for_cell(cell, terrain_id: int = -1) -> int:
+	return dijkstra_map.set_terrain_for_point(get_id_for_cell(cell), terrain_id)
+
+
+func get_terrain_for_cell(cell) -> int:
+	return dijkstra_map.get_terrain_for_point(get_id_for_cell(cell))
+
+
+# Same as `DijkstraMap.disable_point`. `FAILED` if the cell is empty.
+func disable_cell(cell) -> int:
+	return dijkstra_map.disable_point(get_id_for_cell(cell))
+
+
+# Same as `DijkstraMap.enable_point`. `FAILED` if the cell is empty.
+func enable_cell(cell) -> int:
+	return dijkstra_map.enable_point(get_id_for_cell(cell))
+
+
+func is_cell_disabled(cell) -> bool:
+	return dijkstra_map.is_point_disabled(get_id_for_cell(cell))
+
+
+# Returns the cost of each of `cells` (an Array or a PoolVector2Array /
+# PoolVector3Array) in one native call, INF for empty or unreachable
+# cells. Meant for previewing movement costs over a hovered region.
+func get_costs_for_cells(cells) -> PoolRealArray:
+	var ids: PoolIntArray = PoolIntArray()
+	for cell in cells:
+		ids.push_back(get_id_for_cell(cell))
+	return dijkstra_map.get_cost_at_points(ids)
+
+
+# Returns the next cell toward the target, or `null` if there is no path.
+func get_direction_at_cell(cell):
+	return _id_to_cell.get(dijkstra_map.get_direction_at_point(get_id_for_cell(cell)))
+
+
+# Returns the cell reached by the last recalculation that is closest to
+# `target` in a straight line, so agents can get as close as possible to
+# an unreachable destination. This is `target` itself when it was
+# reached, and `null` when nothing was.
+func get_closest_reachable_cell(target):
+	var closest_cell = null
+	var closest_distance: float = INF
+	for id in dijkstra_map.get_cost_map():
+		var cell = _id_to_cell.get(id)
+		if cell != null and cell.distance_squared_to(target) < closest_distance:
+			closest_distance = cell.distance_squared_to(target)
+			closest_cell = cell
+	return closest_cell
+
+
+# Returns the cells along the shortest path, `cell` itself excluded.
+func get_shortest_path_from_cell(cell):
+	var cells: Array = []
+	for id in dijkstra_map.get_shortest_path_from_point(get_id_for_cell(cell)):
+		cells.push_back(_id_to_cell[id])
+	return _to_path(cells)
+
+
+# Returns the path from `from_cell` toward `to_cell`, or when `to_cell`
+# cannot be reached, toward the reachable cell closest to it (see
+# `get_closest_reachable_cell`). `from_cell` is excluded, as in
+# `get_shortest_path_from_cell`. This takes two recalculations and leaves
+# the map computed toward the end of the returned path.
+func get_partial_path(from_cell, to_cell, optional_params: Dictionary = {}):
+	var params: Dictionary = optional_params.duplicate()
+	params["input_is_destination"] = false
+	recalculate(get_id_for_cell(from_cell), params)
+	var end_cell = get_closest_reachable_cell(to_cell)
+	if end_cell == null:
+		return _to_path([])
+	params["input_is_destination"] = true
+	recalculate(get_id_for_cell(end_cell), params)
+	return get_shortest_path_from_cell(from_cell)
+
+
+# Returns cell -> graph distance to the nearest cell that touches an
+# obstacle, for clearance checks and decoration. A cell touches an
+# obstacle when one of its links leads to an empty or disabled cell; such
+# cells get 0. Every terrain counts as 1.0, so only connection weights
+# matter. Disabled and unreachable cells are absent. The search runs on a
+# copy of the graph, so the map's own results are kept.
+func get_obstacle_distance_field() -> Dictionary:
+	var border: PoolIntArray = PoolIntArray()
+	var unit_weights: Dictionary = {}
+	for cell in _cell_to_id:
+		var id: int = _cell_to_id[cell]
+		unit_weights[dijkstra_map.get_terrain_for_point(id)] = 1.0
+		if dijkstra_map.is_point_disabled(id):
+			continue
+		for link in _get_links(cell):
+			if not _cell_to_id.has(link[0]) or dijkstra_map.is_point_disabled(_cell_to_id[link[0]]):
+				border.push_back(id)
+				break
+	var field: Dictionary = {}
+	if border.empty():
+		return field
+	var copy: DijkstraMap = DijkstraMap.new()
+	copy.duplicate_graph_from(dijkstra_map)
+	copy.recalculate(border, {"terrain_weights": unit_weights})
+	var costs: Dictionary = copy.get_cost_map()
+	for id in costs:
+		field[_id_to_cell[id]] = costs[id]
+	return field
+
+
+func _with_defaults(optional_params: Dictionary) -> Dictionary:
+	var params: Dictionary = {
+		"input_is_destination": input_is_destination, "maximum_cost": maximum_cost
+	}
+	if not terrain_weights.empty():
+		params["terrain_weights"] = terrain_weights
+	for key in optional_params:
+		params[key] = optional_params[key]
+	return params
+
+
+# Points `_grid` at the node behind `_get_grid_path()`.
+func _resolve_grid() -> bool:
+	_grid = get_node_or_null(_get_grid_path())
+	if _grid == null or not _grid.is_class(_get_grid_class()):
+		push_error("%s: the grid path does not point to a %s" % [name, _get_grid_class()])
+		_grid = null
+		return false
+	return true
+
+
+func _add_cell(cell) -> void:
+	var id: int = dijkstra_map.get_available_point_id()
+	dijkstra_map.add_point(id, item_terrains.get(_get_cell_item(cell), -1))
+	_cell_to_id[cell] = id
+	_id_to_cell[id] = cell
+
+
+# Connects `cell` to every existing cell it links to.
+func _connect_from(cell) -> void:
+	for link in _get_links(cell):
+		if _cell_to_id.has(link[0]):
+			_connect_link(cell, link)
+
+
+func _connect_link(cell, link: Array) -> void:
+	var weight: float = link[1]
+	if is_inf(weight) or is_nan(weight):
+		return
+	if _elevations.has(cell) and _elevations.has(link[0]):
+		var rise: float = _elevations[link[0]] - _elevations[cell]
+		weight += rise * _uphill_factor if rise > 0 else -rise * _downhill_factor
+	dijkstra_map.connect_points(_cell_to_id[cell], _cell_to_id[link[0]], max(weight, 0.0), false)
+
+
+# Returns the cost of moving one `step`, `default_cost` unless
+# `step_costs` overrides it.
+func _get_step_cost(step, default_cost: float) -> float:
+	return step_costs.get(step, default_cost)
+
+
+# Virtual: path of the grid node.
+func _get_grid_path() -> NodePath:
+	return NodePath()
+
+
+# Virtual: class the grid node must have.
+func _get_grid_class() -> String:
+	return "Node"
+
+
+# Virtual: tile or item at `cell`, `-1` if the cell is empty.
+func _get_cell_item(_cell) -> int:
+	return -1
+
+
+# Virtual: `[other_cell, weight]` pairs for the one-way connections that
+# leave `cell`. Links to empty cells and INF or NAN weights are skipped.
+func _get_links(_cell) -> Array:
+	return []
+
+
+# Virtual: every cell whose links may lead to `cell`.
+func _get_neighbours(_cell) -> Array:
+	return []
+
+
+# Virtual: converts an Array of cells to the subclass's path type.
+func _to_path(cells: Array):
+	return cells
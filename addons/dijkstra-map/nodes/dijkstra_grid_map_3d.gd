@@ -0,0 +1,96 @@
+# Keeps a DijkstraMap in sync with a GridMap and exposes cell-addressed
+# queries.
+#
+# Every used cell becomes a point, with the terrain from `item_terrains`
+# keyed by item ID. Floor cells connect to their horizontal neighbours on
+# the same floor, and to their diagonal ones when `diagonal_cost` is
+# finite. Items listed in `ramp_items` rise one floor in their direction:
+# a ramp connects back to the floor cell behind it and up to the cell
+# past its top, and nothing else on its sides.
+#
+#   # item 4 is a ramp rising toward +x
+#   grid_map_3d.ramp_items = {4: Vector3(1, 0, 0)}
+#   grid_map_3d.rebuild()
+#   grid_map_3d.recalculate(grid_map_3d.get_id_for_cell(goal))
+#   var next_cell: Vector3 = grid_map_3d.get_direction_at_cell(cell)
+#
+# MeshLibrary items have no metadata in Godot 3, so ramps are configured
+# here rather than on the items. After changing cells on the GridMap, call
+# `update_cell` for each of them, or `rebuild` for larger edits.
+class_name DijkstraGridMap3D, "res://addons/dijkstra-map/dm_icon.png"
+extends "res://addons/dijkstra-map/nodes/dijkstra_grid_base.gd"
+
+const HORIZONTAL_STEPS: Array = [
+	Vector3(1, 0, 0), Vector3(-1, 0, 0), Vector3(0, 0, 1), Vector3(0, 0, -1)
+]
+const DIAGONAL_STEPS: Array = [
+	Vector3(1, 0, 1), Vector3(1, 0, -1), Vector3(-1, 0, 1), Vector3(-1, 0, -1)
+]
+
+export var grid_map_path: NodePath
+# Item ID -> horizontal step (Vector3) in which the ramp rises.
+export var ramp_items: Dictionary = {}
+
+
+func _get_grid_path() -> NodePath:
+	return grid_map_path
+
+
+func _get_grid_class() -> String:
+	return "GridMap"
+
+
+func _get_cell_item(cell) -> int:
+	return _grid.get_cell_item(cell.x, cell.y, cell.z)
+
+
+func _get_links(cell) -> Array:
+	var links: Array = []
+	var direction = _get_ramp_direction(cell)
+	if direction != null:
+		# Off the bottom onto a floor cell, or down onto the ramp below.
+		var back: Vector3 = cell - direction
+		if _get_ramp_direction(back) == null:
+			links.push_back([back, _get_step_cost(-direction, orthogonal_cost)])
+		if _get_ramp_direction(back + Vector3.DOWN) == direction:
+			links.push_back([back + Vector3.DOWN, _get_step_cost(-direction, orthogonal_cost)])
+		# Off the top onto a floor cell, or onto a ramp that keeps rising.
+		var top: Vector3 = cell + direction + Vector3.UP
+		var top_direction = _get_ramp_direction(top)
+		if top_direction == null or top_direction == direction:
+			links.push_back([top, _get_step_cost(direction, orthogonal_cost)])
+		return links
+	for step in HORIZONTAL_STEPS:
+		# Onto a neighbour, or onto the bottom of a ramp rising away.
+		var step_direction = _get_ramp_direction(cell + step)
+		if step_direction == null or step_direction == step:
+			links.push_back([cell + step, _get_step_cost(step, orthogonal_cost)])
+		# Onto the top of a ramp rising toward this cell.
+		var below: Vector3 = cell - step + Vector3.DOWN
+		if _get_ramp_direction(below) == step:
+			links.push_back([below, _get_step_cost(-step, orthogonal_cost)])
+	for step in DIAGONAL_STEPS:
+		if _get_ramp_direction(cell + step) == null:
+			links.push_back([cell + step, _get_step_cost(step, diagonal_cost)])
+	return links
+
+
+func _get_neighbours(cell) -> Array:
+	var neighbours: Array = []
+	for x in range(-1, 2):
+		for y in range(-1, 2):
+			for z in range(-1, 2):
+				if x != 0 or y != 0 or z != 0:
+					neighbours.push_back(cell + Vector3(x, y, z))
+	return neighbours
+
+
+# Paths are returned as a PoolVector3Array.
+func _to_path(cells: Array):
+	return PoolVector3Array(cells)
+
+
+# Returns the step in which the ramp at `cell` rises, or `null` if the cell
+# is not a ramp.
+func _get_ramp_direction(cell: Vector3):
+	return ramp_items.get(_get_cell_item(cell))
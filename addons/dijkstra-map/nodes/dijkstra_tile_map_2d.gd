@@ -0,0 +1,141 @@
+# Keeps a DijkstraMap in sync with a TileMap and exposes cell-addressed
+# queries.
+#
+# Every used cell becomes a point, with the terrain from `item_terrains`
+# keyed by tile ID. Cells are connected to their orthogonal neighbours, and
+# to their diagonal ones when `diagonal_cost` is finite:
+#
+#   tile_map_2d.item_terrains = {0: 0, 1: 1}
+#   tile_map_2d.rebuild()
+#   tile_map_2d.recalculate(tile_map_2d.get_id_for_cell(goal), {"terrain_weights": weights})
+#   var next_cell: Vector2 = tile_map_2d.get_direction_at_cell(cell)
+#
+# Godot 3 TileMaps emit no signal when a cell changes, so after `set_cell`
+# call `update_cell` for each edited cell, or `rebuild` for larger edits.
+class_name DijkstraTileMap2D, "res://addons/dijkstra-map/dm_icon.png"
+extends "res://addons/dijkstra-map/nodes/dijkstra_grid_base.gd"
+
+const ORTHOGONAL_STEPS: Array = [Vector2(1, 0), Vector2(-1, 0), Vector2(0, 1), Vector2(0, -1)]
+const DIAGONAL_STEPS: Array = [Vector2(1, 1), Vector2(1, -1), Vector2(-1, 1), Vector2(-1, -1)]
+# Steps in the order of `get_direction_field_cardinal` codes: N, NE, E, SE,
+# S, SW, W, NW, with -y being north.
+const CARDINAL_STEPS: Array = [
+	Vector2(0, -1),
+	Vector2(1, -1),
+	Vector2(1, 0),
+	Vector2(1, 1),
+	Vector2(0, 1),
+	Vector2(-1, 1),
+	Vector2(-1, 0),
+	Vector2(-1, -1)
+]
+const CARDINAL_ORIGIN: int = 8
+const CARDINAL_NONE: int = 255
+
+export var tile_map_path: NodePath
+
+
+# Returns the unit direction toward the next cell at `world_position`,
+# blended bilinearly between the four nearest cell centers for smooth
+# steering. Origins, unreachable and empty cells add nothing to the
+# blend. Returns `Vector2.ZERO` where none of the four cells has a
+# direction.
+func get_interpolated_direction(world_position: Vector2) -> Vector2:
+	if _grid == null:
+		return Vector2.ZERO
+	var cell_size: Vector2 = _grid.cell_size
+	var cell_position: Vector2 = _grid.to_local(world_position) / cell_size - Vector2(0.5, 0.5)
+	var base: Vector2 = cell_position.floor()
+	var weight: Vector2 = cell_position - base
+	var direction: Vector2 = Vector2.ZERO
+	for offset in [Vector2(0, 0), Vector2(1, 0), Vector2(0, 1), Vector2(1, 1)]:
+		var blend_x: float = weight.x if offset.x > 0 else 1.0 - weight.x
+		var blend_y: float = weight.y if offset.y > 0 else 1.0 - weight.y
+		direction += _get_cell_direction(base + offset) * blend_x * blend_y
+	direction = _grid.global_transform.basis_xform(direction * cell_size)
+	return direction.normalized()
+
+
+# Encodes the cells of `rect` into an RGBAF Image, one pixel per cell,
+# for shaders that sample the field:
+#   R, G: the unit step toward the next cell, mapped from [-1, 1] to
+#       [0, 1];
+#   B: the cost divided by `max_cost`, clamped to [0, 1];
+#   A: 1 if the cell was reached, 0 otherwise.
+func get_flow_field_image(rect: Rect2, max_cost: float) -> Image:
+	var image: Image = Image.new()
+	image.create(int(rect.size.x), int(rect.size.y), false, Image.FORMAT_RGBAF)
+	image.lock()
+	for y in range(int(rect.size.y)):
+		for x in range(int(rect.size.x)):
+			var cell: Vector2 = rect.position + Vector2(x, y)
+			var cost: float = get_cost_at_cell(cell)
+			if is_inf(cost):
+				image.set_pixel(x, y, Color(0.5, 0.5, 1.0, 0.0))
+				continue
+			var direction: Vector2 = _get_cell_direction(cell) * 0.5 + Vector2(0.5, 0.5)
+			var ratio: float = clamp(cost / max_cost, 0.0, 1.0) if max_cost > 0.0 else 1.0
+			image.set_pixel(x, y, Color(direction.x, direction.y, ratio, 1.0))
+	image.unlock()
+	return image
+
+
+# Returns one byte per cell of `rect`, row by row: the index in
+# `CARDINAL_STEPS` of the step toward the next cell, `CARDINAL_ORIGIN` for
+# origins and `CARDINAL_NONE` for unreachable and empty cells.
+func get_direction_field_cardinal(rect: Rect2) -> PoolByteArray:
+	var field: PoolByteArray = PoolByteArray()
+	for y in range(int(rect.position.y), int(rect.end.y)):
+		for x in range(int(rect.position.x), int(rect.end.x)):
+			var cell: Vector2 = Vector2(x, y)
+			var next_cell = get_direction_at_cell(cell)
+			if next_cell == null:
+				field.push_back(CARDINAL_NONE)
+			elif next_cell == cell:
+				field.push_back(CARDINAL_ORIGIN)
+			else:
+				var code: int = CARDINAL_STEPS.find(next_cell - cell)
+				field.push_back(CARDINAL_NONE if code == -1 else code)
+	return field
+
+
+func _get_grid_path() -> NodePath:
+	return tile_map_path
+
+
+func _get_grid_class() -> String:
+	return "TileMap"
+
+
+func _get_cell_item(cell) -> int:
+	return _grid.get_cellv(cell)
+
+
+func _get_links(cell) -> Array:
+	var links: Array = []
+	for step in ORTHOGONAL_STEPS:
+		links.push_back([cell + step, _get_step_cost(step, orthogonal_cost)])
+	for step in DIAGONAL_STEPS:
+		links.push_back([cell + step, _get_step_cost(step, diagonal_cost)])
+	return links
+
+
+func _get_neighbours(cell) -> Array:
+	var neighbours: Array = []
+	for step in ORTHOGONAL_STEPS + DIAGONAL_STEPS:
+		neighbours.push_back(cell + step)
+	return neighbours
+
+
+# Paths are returned as a PoolVector2Array.
+func _to_path(cells: Array):
+	return PoolVector2Array(cells)
+
+
+# Returns the unit step toward the next cell, `Vector2.ZERO` for origins,
+# unreachable and empty cells.
+func _get_cell_direction(cell: Vector2) -> Vector2:
+	var next_cell = get_direction_at_cell(cell)
+	if next_cell == null:
+		return Vector2.ZERO
+	return (next_cell - cell).normalized()
@@ -0,0 +1,49 @@
+# Runs every test script listed in `TEST_SCRIPTS` and exits with 1 if
+# anything failed, so it can be used in CI. From the repository root,
+# which holds a minimal project.godot, with a Godot 3.5 binary:
+#
+#   # Linux, with the headless or server build (no display needed)
+#   godot_server --path . -s res://addons/dijkstra-map/tests/run_tests.gd
+#   # Windows, where the regular editor binary accepts --no-window
+#   godot --no-window --path . -s res://addons/dijkstra-map/tests/run_tests.gd
+#
+# `--no-window` only hides the window on Windows; on Linux the regular
+# binary still needs a display, hence the headless/server build.
+extends SceneTree
+
+const DijkstraMapScript = preload("res://addons/dijkstra-map/Dijkstra_map_library/nativescript.gdns")
+const TEST_SCRIPTS: Array = [
+	preload("res://addons/dijkstra-map/tests/test_dijkstra_map.gd"),
+	preload("res://addons/dijkstra-map/tests/test_dijkstra_map_utils.gd"),
+	preload("res://addons/dijkstra-map/tests/test_dijkstra_distance_table.gd"),
+	preload("res://addons/dijkstra-map/tests/test_dijkstra_nodes.gd"),
+]
+
+
+# Runs once the root viewport is in the tree, so tests can add nodes to it.
+func _initialize() -> void:
+	if DijkstraMapScript.new() == null:
+		printerr("could not instance DijkstraMap: is the native library loaded?")
+		quit(1)
+		return
+
+	var test_count: int = 0
+	var failed_tests: int = 0
+	var failures: int = 0
+	for script in TEST_SCRIPTS:
+		var test_case: Reference = script.new()
+		for method in test_case.get_method_list():
+			if not method.name.begins_with("test_"):
+				continue
+			test_case.current_test = "%s:%s" % [script.resource_path.get_file(), method.name]
+			test_count += 1
+			var completed = test_case.call(method.name)
+			if typeof(completed) != TYPE_BOOL or not completed:
+				failed_tests += 1
+				printerr("FAILED %s: did not run to completion" % test_case.current_test)
+		failures += test_case.failures
+	print(
+		"%d tests, %d did not complete, %d failed assertions"
+		% [test_count, failed_tests, failures]
+	)
+	quit(1 if failures > 0 or failed_tests > 0 else 0)
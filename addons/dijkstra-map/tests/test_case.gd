@@ -0,0 +1,34 @@
+# Base of the test scripts that `run_tests.gd` runs.
+#
+# Every method starting with `test_` is run and must return `true` on its
+# last line. A runtime error aborts the test and makes it return null, so a
+# test that does not reach its end is counted as failed.
+extends Reference
+
+var failures: int = 0
+var current_test: String = ""
+
+
+func assert_eq(got, expected, text: String = "") -> void:
+	if typeof(got) != typeof(expected) or got != expected:
+		_fail("expected %s, got %s" % [str(expected), str(got)], text)
+
+
+func assert_almost_eq(got: float, expected: float, text: String = "") -> void:
+	if not is_equal_approx(got, expected):
+		_fail("expected %s, got %s" % [str(expected), str(got)], text)
+
+
+func assert_true(got: bool, text: String = "") -> void:
+	if not got:
+		_fail("expected true", text)
+
+
+func assert_false(got: bool, text: String = "") -> void:
+	if got:
+		_fail("expected false", text)
+
+
+func _fail(reason: String, text: String) -> void:
+	failures += 1
+	printerr("FAILED %s: %s %s" % [current_test, reason, text])
@@ -0,0 +1,34 @@
+# Tests for DijkstraDistanceTable. Run through `run_tests.gd`.
+extends "res://addons/dijkstra-map/tests/test_case.gd"
+
+
+# 0 <---> 1 <---> 2 ----> 3
+#    1.0     2.0     1.0
+func small_map() -> DijkstraMap:
+	var dijkstra_map: DijkstraMap = DijkstraMap.new()
+	for id in range(4):
+		dijkstra_map.add_point(id)
+	dijkstra_map.connect_points(0, 1)
+	dijkstra_map.connect_points(1, 2, 2.0)
+	dijkstra_map.connect_points(2, 3, 1.0, false)
+	return dijkstra_map
+
+
+func test_compute_between_hubs() -> bool:
+	var table: DijkstraDistanceTable = DijkstraDistanceTable.new()
+	table.compute(small_map(), PoolIntArray([0, 3]), PoolIntArray([0, 3]))
+	assert_eq(table.get_cost_between(0, 3), 4.0)
+	assert_eq(table.get_cost_between(3, 0), INF, "the last connection is one-way")
+	assert_eq(table.get_cost_between(0, 0), 0.0)
+	assert_eq(table.get_cost_between(1, 3), INF, "1 is not a source")
+	assert_false(table.has_target(1))
+	return true
+
+
+func test_compute_from_every_point() -> bool:
+	var table: DijkstraDistanceTable = DijkstraDistanceTable.new()
+	table.compute(small_map(), PoolIntArray([0]))
+	assert_eq(table.get_cost_between(2, 0), 3.0)
+	assert_eq(table.get_cost_between(3, 0), INF)
+	assert_true(table.has_target(0))
+	return true
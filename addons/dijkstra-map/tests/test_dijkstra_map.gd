@@ -0,0 +1,277 @@
+# Tests for the DijkstraMap API, following the examples in
+# doc/DijkstraMap.md. Run through `run_tests.gd`.
+extends "res://addons/dijkstra-map/tests/test_case.gd"
+
+
+# Builds the graph used by most query examples:
+# 0 <---> 1    2
+#    1.0
+func line_map() -> DijkstraMap:
+	var dijkstra_map: DijkstraMap = DijkstraMap.new()
+	dijkstra_map.add_point(0)
+	dijkstra_map.add_point(1)
+	dijkstra_map.add_point(2)
+	dijkstra_map.connect_points(0, 1)
+	dijkstra_map.recalculate(0)
+	return dijkstra_map
+
+
+func test_clear() -> bool:
+	var dijkstra_map: DijkstraMap = DijkstraMap.new()
+	dijkstra_map.add_point(0)
+	dijkstra_map.clear()
+	assert_false(dijkstra_map.has_point(0))
+	return true
+
+
+func test_duplicate_graph_from() -> bool:
+	var dijkstra_map: DijkstraMap = DijkstraMap.new()
+	dijkstra_map.add_point(1)
+	dijkstra_map.add_point(2)
+	dijkstra_map.add_point(3)
+	dijkstra_map.connect_points(1, 2, 1.0)
+	var dijkstra_map_copy: DijkstraMap = DijkstraMap.new()
+	assert_eq(dijkstra_map_copy.duplicate_graph_from(dijkstra_map), OK)
+	dijkstra_map.add_point(4)
+	assert_true(dijkstra_map_copy.has_point(1))
+	assert_true(dijkstra_map_copy.has_point(2))
+	assert_true(dijkstra_map_copy.has_point(3))
+	assert_true(dijkstra_map_copy.has_connection(1, 2))
+	assert_false(dijkstra_map_copy.has_point(4))
+	assert_eq(dijkstra_map_copy.duplicate_graph_from(3), FAILED)
+	return true
+
+
+func test_get_available_point_id() -> bool:
+	var dijkstra_map: DijkstraMap = DijkstraMap.new()
+	dijkstra_map.add_point(0)
+	dijkstra_map.add_point(1)
+	assert_eq(dijkstra_map.get_available_point_id(), 2)
+	return true
+
+
+func test_add_point() -> bool:
+	var dijkstra_map: DijkstraMap = DijkstraMap.new()
+	assert_eq(dijkstra_map.add_point(0), OK)
+	assert_eq(dijkstra_map.get_terrain_for_point(0), -1, "default terrain_type is -1")
+	assert_eq(dijkstra_map.add_point(1, 0), OK, "you may add a point once")
+	assert_eq(dijkstra_map.add_point(1, 0), FAILED, "but not twice")
+	assert_eq(dijkstra_map.add_point(1, 1), FAILED, "you cannot even change the terrain this way")
+	return true
+
+
+func test_terrain_for_point() -> bool:
+	var dijkstra_map: DijkstraMap = DijkstraMap.new()
+	dijkstra_map.add_point(0, 2)
+	assert_eq(dijkstra_map.set_terrain_for_point(0, 1), OK)
+	assert_eq(dijkstra_map.get_terrain_for_point(0), 1)
+	assert_eq(dijkstra_map.set_terrain_for_point(0), OK)
+	assert_eq(dijkstra_map.get_terrain_for_point(0), -1, "default terrain is -1")
+	assert_eq(dijkstra_map.set_terrain_for_point(1, 0), FAILED, "1 is not in the map")
+	assert_eq(dijkstra_map.get_terrain_for_point(1), -1, "1 is not in the map")
+	return true
+
+
+func test_remove_point() -> bool:
+	var dijkstra_map: DijkstraMap = DijkstraMap.new()
+	dijkstra_map.add_point(0)
+	assert_eq(dijkstra_map.remove_point(0), OK)
+	assert_eq(dijkstra_map.remove_point(0), FAILED)
+	assert_false(dijkstra_map.has_point(0))
+	return true
+
+
+func test_disable_and_enable_point() -> bool:
+	var dijkstra_map: DijkstraMap = DijkstraMap.new()
+	dijkstra_map.add_point(0)
+	dijkstra_map.add_point(1)
+	assert_eq(dijkstra_map.disable_point(0), OK)
+	assert_eq(dijkstra_map.disable_point(2), FAILED)
+	assert_true(dijkstra_map.is_point_disabled(0))
+	assert_false(dijkstra_map.is_point_disabled(1), "not disabled")
+	assert_false(dijkstra_map.is_point_disabled(2), "not in the map")
+	assert_eq(dijkstra_map.enable_point(0), OK)
+	assert_eq(dijkstra_map.enable_point(2), FAILED)
+	assert_false(dijkstra_map.is_point_disabled(0))
+	return true
+
+
+func test_connect_points() -> bool:
+	var dijkstra_map: DijkstraMap = DijkstraMap.new()
+	dijkstra_map.add_point(0)
+	dijkstra_map.add_point(1)
+	dijkstra_map.add_point(2)
+	dijkstra_map.add_point(3)
+	assert_eq(dijkstra_map.connect_points(0, 1, 2.0), OK)
+	assert_eq(dijkstra_map.connect_points(1, 2), OK)
+	assert_eq(dijkstra_map.connect_points(1, 3, 1.0, false), OK)
+	assert_true(dijkstra_map.has_connection(1, 0), "bidirectional is enabled by default")
+	assert_true(dijkstra_map.has_connection(1, 3))
+	assert_false(dijkstra_map.has_connection(3, 1))
+	assert_eq(dijkstra_map.connect_points(1, 4), FAILED, "4 does not exists in the map")
+	return true
+
+
+func test_remove_connection() -> bool:
+	var dijkstra_map: DijkstraMap = DijkstraMap.new()
+	dijkstra_map.add_point(0)
+	dijkstra_map.add_point(1)
+	dijkstra_map.connect_points(0, 1)
+	assert_eq(dijkstra_map.remove_connection(0, 1), OK)
+	assert_false(dijkstra_map.has_connection(1, 0))
+	assert_eq(dijkstra_map.remove_connection(0, 2), FAILED, "2 does not exists in the map")
+	dijkstra_map.connect_points(0, 1)
+	assert_eq(dijkstra_map.remove_connection(0, 1, false), OK)
+	assert_false(dijkstra_map.has_connection(0, 1))
+	assert_true(dijkstra_map.has_connection(1, 0))
+	return true
+
+
+func test_get_direction_at_point() -> bool:
+	var dijkstra_map: DijkstraMap = line_map()
+	assert_eq(dijkstra_map.get_direction_at_point(0), 0)
+	assert_eq(dijkstra_map.get_direction_at_point(1), 0)
+	assert_eq(dijkstra_map.get_direction_at_point(2), -1)
+	assert_eq(Array(dijkstra_map.get_direction_at_points(PoolIntArray([0, 1, 2]))), [0, 0, -1])
+	return true
+
+
+func test_get_cost_at_point() -> bool:
+	var dijkstra_map: DijkstraMap = line_map()
+	assert_eq(dijkstra_map.get_cost_at_point(0), 0.0)
+	assert_eq(dijkstra_map.get_cost_at_point(1), 1.0)
+	assert_eq(dijkstra_map.get_cost_at_point(2), INF)
+	assert_eq(Array(dijkstra_map.get_cost_at_points(PoolIntArray([0, 1, 2]))), [0.0, 1.0, INF])
+	return true
+
+
+func test_cost_and_direction_maps() -> bool:
+	var dijkstra_map: DijkstraMap = line_map()
+	# Dictionaries compare by reference in Godot 3, so check entries one by one.
+	var cost_map: Dictionary = dijkstra_map.get_cost_map()
+	assert_eq(cost_map.size(), 2, "2 is unreachable")
+	assert_eq(cost_map[0], 0.0)
+	assert_eq(cost_map[1], 1.0)
+	var direction_map: Dictionary = dijkstra_map.get_direction_map()
+	assert_eq(direction_map.size(), 2, "2 is unreachable")
+	assert_eq(direction_map[0], 0)
+	assert_eq(direction_map[1], 0)
+	return true
+
+
+func test_get_all_points_with_cost_between() -> bool:
+	var dijkstra_map: DijkstraMap = line_map()
+	assert_eq(Array(dijkstra_map.get_all_points_with_cost_between(0.5, 1.5)), [1])
+	return true
+
+
+func test_get_shortest_path_from_point() -> bool:
+	var dijkstra_map: DijkstraMap = line_map()
+	dijkstra_map.connect_points(1, 2)
+	dijkstra_map.recalculate(0)
+	assert_eq(Array(dijkstra_map.get_shortest_path_from_point(2)), [1, 0])
+	assert_eq(Array(dijkstra_map.get_shortest_path_from_point(0)), [], "0 is the target")
+	return true
+
+
+func test_recalculate_options() -> bool:
+	var dijkstra_map: DijkstraMap = DijkstraMap.new()
+	dijkstra_map.add_point(0, 0)
+	dijkstra_map.add_point(1, 1)
+	dijkstra_map.add_point(2, 0)
+	dijkstra_map.connect_points(0, 1)
+	dijkstra_map.connect_points(1, 2, 10.0)
+	var optional_params = {
+		"terrain_weights": {0: 1.0, 1: 2.0},
+		"input_is_destination": true,
+		"maximum_cost": 2.0,
+	}
+	assert_eq(dijkstra_map.recalculate(0, optional_params), OK)
+	assert_eq(dijkstra_map.get_direction_at_point(0), 0)
+	assert_eq(dijkstra_map.get_direction_at_point(1), 0)
+	assert_eq(dijkstra_map.get_direction_at_point(2), -1, "2 is beyond maximum_cost")
+	assert_eq(dijkstra_map.recalculate(0, {"terain_weights": {}}), FAILED, "invalid key")
+	assert_eq(dijkstra_map.recalculate("0"), FAILED, "invalid origin")
+	return true
+
+
+# 0 ----> 1
+#    1.0
+func test_recalculate_input_is_destination() -> bool:
+	var dijkstra_map: DijkstraMap = DijkstraMap.new()
+	dijkstra_map.add_point(0)
+	dijkstra_map.add_point(1)
+	dijkstra_map.connect_points(0, 1, 1.0, false)
+
+	assert_eq(dijkstra_map.recalculate(0, {"input_is_destination": true}), OK)
+	assert_eq(dijkstra_map.get_cost_at_point(0), 0.0)
+	assert_eq(dijkstra_map.get_cost_at_point(1), INF, "there is no connection from 1 to 0")
+	assert_eq(dijkstra_map.get_direction_at_point(1), -1)
+
+	assert_eq(dijkstra_map.recalculate(0, {"input_is_destination": false}), OK)
+	assert_eq(dijkstra_map.get_cost_at_point(0), 0.0)
+	assert_eq(dijkstra_map.get_cost_at_point(1), 1.0, "0 is now the start of the path")
+
+	assert_eq(dijkstra_map.recalculate(1, {"input_is_destination": false}), OK)
+	assert_eq(dijkstra_map.get_cost_at_point(0), INF, "there is no connection from 1 to 0")
+	return true
+
+
+# 0 <---> 1 <---> 2 <---> 3 <---> 4
+#    1.0     1.0     1.0     1.0
+func test_recalculate_termination_points() -> bool:
+	var dijkstra_map: DijkstraMap = DijkstraMap.new()
+	for id in range(5):
+		dijkstra_map.add_point(id)
+	for id in range(4):
+		dijkstra_map.connect_points(id, id + 1)
+
+	assert_eq(dijkstra_map.recalculate(0), OK)
+	assert_eq(dijkstra_map.get_cost_at_point(3), 3.0, "reachable without termination points")
+
+	assert_eq(dijkstra_map.recalculate(0, {"termination_points": 1}), OK)
+	assert_eq(dijkstra_map.get_cost_at_point(0), 0.0)
+	assert_eq(dijkstra_map.get_cost_at_point(3), INF, "the search stopped at 1")
+
+	assert_eq(dijkstra_map.recalculate(0, {"termination_points": [2]}), OK)
+	assert_eq(dijkstra_map.get_cost_at_point(1), 1.0)
+	assert_eq(dijkstra_map.get_cost_at_point(4), INF, "the search stopped at 2")
+	return true
+
+
+func test_recalculate_origin_arrays() -> bool:
+	var dijkstra_map: DijkstraMap = line_map()
+	assert_eq(dijkstra_map.recalculate(PoolIntArray([0, 2])), OK)
+	assert_eq(dijkstra_map.get_cost_at_point(2), 0.0)
+	assert_eq(dijkstra_map.recalculate([1, 2], {"initial_costs": [0.0, 5.0]}), OK)
+	assert_eq(dijkstra_map.get_cost_at_point(0), 1.0)
+	assert_eq(dijkstra_map.get_cost_at_point(2), 5.0)
+	return true
+
+
+func test_add_square_grid() -> bool:
+	var dijkstra_map: DijkstraMap = DijkstraMap.new()
+	var pos_to_id: Dictionary = dijkstra_map.add_square_grid(Rect2(0, 0, 3, 2))
+	assert_eq(pos_to_id.size(), 6)
+	var corner: int = pos_to_id[Vector2(0, 0)]
+	assert_true(dijkstra_map.has_connection(corner, pos_to_id[Vector2(1, 0)]))
+	assert_true(dijkstra_map.has_connection(corner, pos_to_id[Vector2(0, 1)]))
+	assert_false(
+		dijkstra_map.has_connection(corner, pos_to_id[Vector2(1, 1)]), "no diagonals by default"
+	)
+
+	dijkstra_map.clear()
+	pos_to_id = dijkstra_map.add_square_grid(Rect2(0, 0, 2, 2), -1, 1.0, 1.5)
+	dijkstra_map.recalculate(pos_to_id[Vector2(0, 0)])
+	assert_eq(dijkstra_map.get_cost_at_point(pos_to_id[Vector2(1, 1)]), 1.5)
+	return true
+
+
+func test_add_hexagonal_grid() -> bool:
+	var dijkstra_map: DijkstraMap = DijkstraMap.new()
+	var pos_to_id: Dictionary = dijkstra_map.add_hexagonal_grid(Rect2(1, 4, 2, 3))
+	assert_eq(pos_to_id.size(), 6)
+	assert_true(dijkstra_map.has_connection(pos_to_id[Vector2(1, 4)], pos_to_id[Vector2(2, 4)]))
+	assert_true(dijkstra_map.has_connection(pos_to_id[Vector2(1, 4)], pos_to_id[Vector2(1, 5)]))
+	return true
+
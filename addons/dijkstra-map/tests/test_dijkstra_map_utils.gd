@@ -0,0 +1,153 @@
+# Tests for DijkstraMapUtils. Run through `run_tests.gd`.
+extends "res://addons/dijkstra-map/tests/test_case.gd"
+
+
+# Builds a line of points, each connected both ways to the next one:
+# 0 <---> 1 <---> 2 <---> ...
+#    1.0     1.0
+func line_map(size: int) -> DijkstraMap:
+	var dijkstra_map: DijkstraMap = DijkstraMap.new()
+	for id in range(size):
+		dijkstra_map.add_point(id)
+	for id in range(size - 1):
+		dijkstra_map.connect_points(id, id + 1)
+	return dijkstra_map
+
+
+func test_recalculate_with_initial_costs() -> bool:
+	var dijkstra_map: DijkstraMap = line_map(5)
+	assert_eq(DijkstraMapUtils.recalculate_with_initial_costs(dijkstra_map, {4: 0.0, 0: 3.0}), OK)
+	assert_eq(dijkstra_map.get_cost_at_point(0), 3.0, "costs follow their key, not their position")
+	assert_eq(dijkstra_map.get_cost_at_point(1), 3.0)
+	assert_eq(dijkstra_map.get_cost_at_point(2), 2.0)
+	var params: Dictionary = {"initial_costs": [1.0]}
+	assert_eq(DijkstraMapUtils.recalculate_with_initial_costs(dijkstra_map, {0: 0.0}, params), FAILED)
+	return true
+
+
+func test_get_path_from_point() -> bool:
+	var dijkstra_map: DijkstraMap = line_map(5)
+	dijkstra_map.add_point(5)
+	dijkstra_map.recalculate(0)
+	assert_eq(Array(DijkstraMapUtils.get_path_from_point(dijkstra_map, 4)), [3, 2, 1, 0])
+	assert_eq(Array(DijkstraMapUtils.get_path_from_point(dijkstra_map, 4, 2)), [3, 2])
+	assert_eq(Array(DijkstraMapUtils.get_path_from_point(dijkstra_map, 4, 2, true)), [4, 3, 2])
+	var path: Array = Array(DijkstraMapUtils.get_path_from_point(dijkstra_map, 0, -1, true))
+	assert_eq(path, [0], "0 is the target")
+	path = Array(DijkstraMapUtils.get_path_from_point(dijkstra_map, 5, -1, true))
+	assert_eq(path, [], "5 is unreachable")
+	return true
+
+
+func test_get_path_cost_breakdown_from_point() -> bool:
+	var dijkstra_map: DijkstraMap = DijkstraMap.new()
+	dijkstra_map.add_point(0)
+	dijkstra_map.add_point(1)
+	dijkstra_map.add_point(2)
+	dijkstra_map.connect_points(0, 1, 2.0)
+	dijkstra_map.connect_points(1, 2, 3.0)
+	dijkstra_map.recalculate(0)
+	var breakdown: Dictionary = DijkstraMapUtils.get_path_cost_breakdown_from_point(dijkstra_map, 2)
+	assert_eq(Array(breakdown["points"]), [1, 0])
+	assert_eq(Array(breakdown["segment_costs"]), [3.0, 2.0])
+	assert_eq(Array(breakdown["cumulative_costs"]), [3.0, 5.0])
+	return true
+
+
+func test_get_points_with_costs_between() -> bool:
+	var dijkstra_map: DijkstraMap = line_map(4)
+	dijkstra_map.recalculate(0)
+	var result: Dictionary = DijkstraMapUtils.get_points_with_costs_between(dijkstra_map, 0.5, 2.5)
+	assert_eq(Array(result["points"]), [1, 2])
+	assert_eq(Array(result["costs"]), [1.0, 2.0])
+	return true
+
+
+func test_get_reachability_mask() -> bool:
+	var dijkstra_map: DijkstraMap = line_map(2)
+	dijkstra_map.add_point(2)
+	dijkstra_map.recalculate(0)
+	var point_ids: PoolIntArray = PoolIntArray([0, 1, 2, 7])
+	assert_eq(Array(DijkstraMapUtils.get_reachability_mask(dijkstra_map, point_ids)), [1, 1, 0, 0])
+	return true
+
+
+func test_origins() -> bool:
+	var dijkstra_map: DijkstraMap = line_map(5)
+	dijkstra_map.recalculate(PoolIntArray([0, 4]))
+	assert_true(DijkstraMapUtils.is_origin(dijkstra_map, 0))
+	assert_false(DijkstraMapUtils.is_origin(dijkstra_map, 2))
+	var origins: Array = Array(DijkstraMapUtils.get_origins(dijkstra_map))
+	origins.sort()
+	assert_eq(origins, [0, 4])
+	return true
+
+
+func test_get_direction_info() -> bool:
+	var dijkstra_map: DijkstraMap = line_map(3)
+	dijkstra_map.add_point(3)
+	dijkstra_map.disable_point(2)
+	dijkstra_map.recalculate(0)
+	var info: Dictionary = DijkstraMapUtils.get_direction_info(dijkstra_map, 1)
+	assert_eq(info["status"], DijkstraMapUtils.DirectionStatus.NEXT_POINT)
+	assert_eq(info["next_point"], 0)
+	info = DijkstraMapUtils.get_direction_info(dijkstra_map, 0)
+	assert_eq(info["status"], DijkstraMapUtils.DirectionStatus.ORIGIN)
+	assert_eq(info["next_point"], 0)
+	info = DijkstraMapUtils.get_direction_info(dijkstra_map, 2)
+	assert_eq(info["status"], DijkstraMapUtils.DirectionStatus.DISABLED_POINT)
+	info = DijkstraMapUtils.get_direction_info(dijkstra_map, 3)
+	assert_eq(info["status"], DijkstraMapUtils.DirectionStatus.NO_PATH)
+	assert_eq(info["next_point"], -1)
+	info = DijkstraMapUtils.get_direction_info(dijkstra_map, 9)
+	assert_eq(info["status"], DijkstraMapUtils.DirectionStatus.MISSING_POINT)
+	return true
+
+
+func test_get_path_through_points() -> bool:
+	var dijkstra_map: DijkstraMap = line_map(5)
+	var route: Dictionary = DijkstraMapUtils.get_path_through_points(dijkstra_map, 2, [4, 0], 1)
+	assert_eq(Array(route["path"]), [3, 4, 3, 2, 1, 0, 1])
+	assert_eq(route["cost"], 7.0)
+	dijkstra_map.add_point(5)
+	route = DijkstraMapUtils.get_path_through_points(dijkstra_map, 0, [5], 1)
+	assert_eq(Array(route["path"]), [], "5 is unreachable")
+	assert_eq(route["cost"], INF)
+	return true
+
+
+func test_has_path() -> bool:
+	var dijkstra_map: DijkstraMap = line_map(3)
+	dijkstra_map.add_point(3)
+	dijkstra_map.connect_points(2, 3, 1.0, false)
+	assert_true(DijkstraMapUtils.has_path(dijkstra_map, 0, 2))
+	assert_true(DijkstraMapUtils.has_path(dijkstra_map, 2, 3))
+	assert_false(DijkstraMapUtils.has_path(dijkstra_map, 3, 2), "the connection is one-way")
+	assert_false(DijkstraMapUtils.has_path(dijkstra_map, 0, 9), "9 is not in the map")
+	return true
+
+
+func test_farthest_points() -> bool:
+	var dijkstra_map: DijkstraMap = line_map(5)
+	dijkstra_map.recalculate(2)
+	assert_eq(Array(DijkstraMapUtils.get_points_with_max_cost(dijkstra_map)).size(), 2)
+	var farthest_point: int = DijkstraMapUtils.get_farthest_point(dijkstra_map)
+	assert_true(farthest_point == 0 or farthest_point == 4)
+	dijkstra_map.clear()
+	assert_eq(DijkstraMapUtils.get_farthest_point(dijkstra_map), -1, "nothing was reached")
+	return true
+
+
+func test_verify_field_consistency() -> bool:
+	var dijkstra_map: DijkstraMap = line_map(4)
+	dijkstra_map.recalculate(0)
+	assert_eq(Array(DijkstraMapUtils.verify_field_consistency(dijkstra_map)), [])
+	dijkstra_map.add_point(4)
+	dijkstra_map.connect_points(3, 4, 0.0)
+	dijkstra_map.recalculate(0)
+	assert_eq(
+		Array(DijkstraMapUtils.verify_field_consistency(dijkstra_map)),
+		[4],
+		"a weight of 0 does not lower the cost"
+	)
+	return true
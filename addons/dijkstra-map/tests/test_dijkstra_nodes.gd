@@ -0,0 +1,275 @@
+# Tests for the GDScript nodes under nodes/. Run through `run_tests.gd`;
+# nodes are added under the SceneTree root, so `_ready` runs as in a game.
+extends "res://addons/dijkstra-map/tests/test_case.gd"
+
+
+# Adds a TileMap with the given cell -> tile Dictionary and a
+# DijkstraTileMap2D wrapping it under the root. Free the returned node's
+# parent when done.
+func add_tile_map_2d(cells: Dictionary, item_terrains: Dictionary = {}) -> DijkstraTileMap2D:
+	var parent: Node = Node.new()
+	var tile_map: TileMap = TileMap.new()
+	tile_map.name = "TileMap"
+	for cell in cells:
+		tile_map.set_cellv(cell, cells[cell])
+	parent.add_child(tile_map)
+	var tile_map_2d: DijkstraTileMap2D = DijkstraTileMap2D.new()
+	tile_map_2d.tile_map_path = NodePath("../TileMap")
+	tile_map_2d.item_terrains = item_terrains
+	parent.add_child(tile_map_2d)
+	Engine.get_main_loop().root.add_child(parent)
+	return tile_map_2d
+
+
+func test_tile_map_terrains() -> bool:
+	var tile_map_2d: DijkstraTileMap2D = add_tile_map_2d({Vector2(0, 0): 0, Vector2(1, 0): 5}, {0: 2})
+	var dijkstra_map: DijkstraMap = tile_map_2d.dijkstra_map
+	assert_eq(dijkstra_map.get_terrain_for_point(tile_map_2d.get_id_for_cell(Vector2(0, 0))), 2)
+	assert_eq(
+		dijkstra_map.get_terrain_for_point(tile_map_2d.get_id_for_cell(Vector2(1, 0))),
+		-1,
+		"unlisted tiles use terrain -1"
+	)
+	tile_map_2d.get_parent().free()
+	return true
+
+
+func test_tile_map_update_cell() -> bool:
+	var tile_map_2d: DijkstraTileMap2D = add_tile_map_2d(
+		{Vector2(0, 0): 0, Vector2(1, 0): 0, Vector2(2, 0): 0}
+	)
+	var tile_map: TileMap = tile_map_2d.get_node("../TileMap")
+	var origin: int = tile_map_2d.get_id_for_cell(Vector2(0, 0))
+	tile_map_2d.recalculate(origin)
+	assert_eq(tile_map_2d.get_cost_at_cell(Vector2(2, 0)), 2.0)
+	var path: Array = Array(tile_map_2d.get_shortest_path_from_cell(Vector2(2, 0)))
+	assert_eq(path, [Vector2(1, 0), Vector2(0, 0)])
+
+	tile_map.set_cellv(Vector2(1, 0), TileMap.INVALID_CELL)
+	tile_map_2d.update_cell(Vector2(1, 0))
+	assert_eq(tile_map_2d.get_id_for_cell(Vector2(1, 0)), -1)
+	tile_map_2d.recalculate(origin)
+	assert_eq(tile_map_2d.get_cost_at_cell(Vector2(2, 0)), INF, "the middle cell is gone")
+
+	tile_map.set_cellv(Vector2(1, 0), 0)
+	tile_map_2d.update_cell(Vector2(1, 0))
+	tile_map_2d.recalculate(origin)
+	assert_eq(tile_map_2d.get_cost_at_cell(Vector2(2, 0)), 2.0, "both neighbours link back")
+	tile_map_2d.get_parent().free()
+	return true
+
+
+func test_tile_map_default_options() -> bool:
+	var tile_map_2d: DijkstraTileMap2D = add_tile_map_2d(
+		{Vector2(0, 0): 0, Vector2(1, 0): 0, Vector2(2, 0): 0}
+	)
+	var origin: int = tile_map_2d.get_id_for_cell(Vector2(0, 0))
+	tile_map_2d.maximum_cost = 1.5
+	tile_map_2d.recalculate(origin)
+	assert_eq(tile_map_2d.get_cost_at_cell(Vector2(2, 0)), INF, "beyond the exported maximum_cost")
+	tile_map_2d.recalculate(origin, {"maximum_cost": INF})
+	assert_eq(tile_map_2d.get_cost_at_cell(Vector2(2, 0)), 2.0, "passed options take precedence")
+	tile_map_2d.get_parent().free()
+	return true
+
+
+func test_tile_map_without_grid() -> bool:
+	var tile_map_2d: DijkstraTileMap2D = DijkstraTileMap2D.new()
+	tile_map_2d.tile_map_path = NodePath("../Missing")
+	Engine.get_main_loop().root.add_child(tile_map_2d)
+	# Both push an error and leave the graph empty instead of crashing.
+	tile_map_2d.update_cell(Vector2(0, 0))
+	tile_map_2d.rebuild()
+	assert_eq(tile_map_2d.get_id_for_cell(Vector2(0, 0)), -1)
+	tile_map_2d.free()
+	return true
+
+
+# Side view (x to the right, y up), with a ramp R rising toward +x and a
+# floor cell S beside the ramp at z = 1:
+#   y = 1      . . T
+#   y = 0      A R .
+func test_grid_map_ramps() -> bool:
+	var parent: Node = Node.new()
+	var grid_map: GridMap = GridMap.new()
+	grid_map.name = "GridMap"
+	grid_map.set_cell_item(0, 0, 0, 0)  # A
+	grid_map.set_cell_item(1, 0, 0, 4)  # R
+	grid_map.set_cell_item(2, 1, 0, 0)  # T
+	grid_map.set_cell_item(1, 0, 1, 0)  # S
+	parent.add_child(grid_map)
+	var grid_map_3d: DijkstraGridMap3D = DijkstraGridMap3D.new()
+	grid_map_3d.grid_map_path = NodePath("../GridMap")
+	grid_map_3d.ramp_items = {4: Vector3(1, 0, 0)}
+	parent.add_child(grid_map_3d)
+	Engine.get_main_loop().root.add_child(parent)
+
+	var dijkstra_map: DijkstraMap = grid_map_3d.dijkstra_map
+	var a: int = grid_map_3d.get_id_for_cell(Vector3(0, 0, 0))
+	var r: int = grid_map_3d.get_id_for_cell(Vector3(1, 0, 0))
+	var t: int = grid_map_3d.get_id_for_cell(Vector3(2, 1, 0))
+	var s: int = grid_map_3d.get_id_for_cell(Vector3(1, 0, 1))
+	assert_eq(dijkstra_map.get_terrain_for_point(a), -1, "unlisted items use terrain -1")
+	assert_true(dijkstra_map.has_connection(a, r), "onto the bottom of the ramp")
+	assert_true(dijkstra_map.has_connection(r, a))
+	assert_true(dijkstra_map.has_connection(r, t), "off the top of the ramp")
+	assert_true(dijkstra_map.has_connection(t, r))
+	assert_false(dijkstra_map.has_connection(s, r), "not onto the side of the ramp")
+	assert_false(dijkstra_map.has_connection(r, s))
+	assert_false(dijkstra_map.has_connection(a, s), "diagonal_cost is INF by default")
+	grid_map_3d.recalculate(t)
+	var path: Array = Array(grid_map_3d.get_shortest_path_from_cell(Vector3(0, 0, 0)))
+	assert_eq(path, [Vector3(1, 0, 0), Vector3(2, 1, 0)])
+	parent.free()
+	return true
+
+
+func test_debug_overlay_dump_grid() -> bool:
+	var dijkstra_map: DijkstraMap = DijkstraMap.new()
+	var overlay: DijkstraDebugOverlay = DijkstraDebugOverlay.new()
+	overlay.pos_to_id = dijkstra_map.add_square_grid(Rect2(0, 0, 3, 1))
+	overlay.dijkstra_map = dijkstra_map
+	dijkstra_map.add_point(10)
+	overlay.pos_to_id[Vector2(0, 1)] = 10
+	dijkstra_map.recalculate(overlay.pos_to_id[Vector2(0, 0)])
+	assert_eq(overlay.dump_grid(Rect2(0, 0, 4, 2)), "* 1 2 #\n. # # #")
+	assert_eq(overlay.dump_grid(Rect2(0, 0, 3, 1), true), "* ← ←")
+	overlay.free()
+	return true
+
+
+func test_agent() -> bool:
+	var dijkstra_map: DijkstraMap = DijkstraMap.new()
+	dijkstra_map.add_point(0)
+	dijkstra_map.add_point(1)
+	dijkstra_map.connect_points(0, 1)
+	var agent: DijkstraAgent2D = DijkstraAgent2D.new()
+	Engine.get_main_loop().root.add_child(agent)
+	agent.target_point = 0
+	agent.dijkstra_map = dijkstra_map
+	assert_eq(dijkstra_map.get_direction_at_point(1), 0, "the pending target was recalculated")
+
+	agent.point_positions = {1: Vector2(0, 0)}
+	agent.snap_to_nearest_point()
+	assert_eq(agent.current_point, 1)
+	agent._physics_process(0.0)
+	assert_eq(agent.get_next_point(), -1, "0 has no position")
+	assert_eq(agent.velocity, Vector2.ZERO)
+
+	agent.point_positions[0] = Vector2(100, 0)
+	agent._physics_process(0.0)
+	assert_eq(agent.get_next_point(), 0)
+	assert_eq(agent.velocity, Vector2(agent.max_speed, 0))
+	agent.free()
+	return true
+
+
+func is_not_middle_cell(cell: Vector2) -> bool:
+	return cell != Vector2(1, 0)
+
+
+func test_tile_map_recalculate_filtered() -> bool:
+	var tile_map_2d: DijkstraTileMap2D = add_tile_map_2d(
+		{Vector2(0, 0): 0, Vector2(1, 0): 0, Vector2(2, 0): 0}
+	)
+	var origin: int = tile_map_2d.get_id_for_cell(Vector2(0, 0))
+	tile_map_2d.recalculate_filtered(origin, funcref(self, "is_not_middle_cell"))
+	assert_eq(tile_map_2d.get_cost_at_cell(Vector2(2, 0)), INF, "the middle cell was left out")
+	var middle: int = tile_map_2d.get_id_for_cell(Vector2(1, 0))
+	assert_false(tile_map_2d.dijkstra_map.is_point_disabled(middle), "enabled again afterwards")
+	tile_map_2d.get_parent().free()
+	return true
+
+
+func test_tile_map_step_costs() -> bool:
+	var tile_map_2d: DijkstraTileMap2D = add_tile_map_2d(
+		{Vector2(0, 0): 0, Vector2(1, 0): 0, Vector2(2, 0): 0}
+	)
+	tile_map_2d.step_costs = {Vector2(1, 0): 0.5, Vector2(-1, 0): INF}
+	tile_map_2d.rebuild()
+	tile_map_2d.recalculate(tile_map_2d.get_id_for_cell(Vector2(2, 0)))
+	assert_eq(tile_map_2d.get_cost_at_cell(Vector2(0, 0)), 1.0, "moving east costs 0.5")
+	tile_map_2d.recalculate(tile_map_2d.get_id_for_cell(Vector2(0, 0)))
+	assert_eq(tile_map_2d.get_cost_at_cell(Vector2(2, 0)), INF, "moving west is disabled")
+	tile_map_2d.get_parent().free()
+	return true
+
+
+func test_tile_map_cell_variants() -> bool:
+	var tile_map_2d: DijkstraTileMap2D = add_tile_map_2d({Vector2(0, 0): 0, Vector2(1, 0): 0})
+	assert_eq(tile_map_2d.set_terrain_for_cell(Vector2(1, 0), 3), OK)
+	assert_eq(tile_map_2d.get_terrain_for_cell(Vector2(1, 0)), 3)
+	assert_eq(tile_map_2d.set_terrain_for_cell(Vector2(5, 5), 3), FAILED, "empty cell")
+	assert_eq(tile_map_2d.disable_cell(Vector2(1, 0)), OK)
+	assert_true(tile_map_2d.is_cell_disabled(Vector2(1, 0)))
+	tile_map_2d.recalculate(tile_map_2d.get_id_for_cell(Vector2(0, 0)))
+	assert_eq(tile_map_2d.get_direction_at_cell(Vector2(1, 0)), null)
+	assert_eq(tile_map_2d.enable_cell(Vector2(1, 0)), OK)
+	assert_false(tile_map_2d.is_cell_disabled(Vector2(1, 0)))
+	tile_map_2d.get_parent().free()
+	return true
+
+
+func test_tile_map_compute_all_pairs() -> bool:
+	var tile_map_2d: DijkstraTileMap2D = add_tile_map_2d(
+		{Vector2(0, 0): 0, Vector2(1, 0): 0, Vector2(2, 0): 0}
+	)
+	var table: DijkstraDistanceTable = tile_map_2d.compute_all_pairs()
+	var left: int = tile_map_2d.get_id_for_cell(Vector2(0, 0))
+	var right: int = tile_map_2d.get_id_for_cell(Vector2(2, 0))
+	assert_eq(table.get_cost_between(left, right), 2.0)
+	assert_eq(table.get_cost_between(right, left), 2.0)
+	assert_eq(tile_map_2d.compute_all_pairs(2), null, "over max_points")
+	tile_map_2d.get_parent().free()
+	return true
+
+
+func test_tile_map_get_closest_reachable_cell() -> bool:
+	var tile_map_2d: DijkstraTileMap2D = add_tile_map_2d(
+		{Vector2(0, 0): 0, Vector2(1, 0): 0, Vector2(3, 0): 0}
+	)
+	tile_map_2d.recalculate(tile_map_2d.get_id_for_cell(Vector2(0, 0)))
+	assert_eq(tile_map_2d.get_closest_reachable_cell(Vector2(3, 0)), Vector2(1, 0), "3, 0 is cut off")
+	assert_eq(tile_map_2d.get_closest_reachable_cell(Vector2(0, 0)), Vector2(0, 0))
+	tile_map_2d.get_parent().free()
+	return true
+
+
+func test_tile_map_get_partial_path() -> bool:
+	var tile_map_2d: DijkstraTileMap2D = add_tile_map_2d(
+		{Vector2(0, 0): 0, Vector2(1, 0): 0, Vector2(2, 0): 0, Vector2(4, 0): 0}
+	)
+	var path: Array = Array(tile_map_2d.get_partial_path(Vector2(0, 0), Vector2(4, 0)))
+	assert_eq(path, [Vector2(1, 0), Vector2(2, 0)], "as close as possible to 4, 0")
+	path = Array(tile_map_2d.get_partial_path(Vector2(0, 0), Vector2(1, 0)))
+	assert_eq(path, [Vector2(1, 0)], "reachable targets get the full path")
+	tile_map_2d.get_parent().free()
+	return true
+
+
+func test_tile_map_get_obstacle_distance_field() -> bool:
+	var cells: Dictionary = {}
+	for x in range(5):
+		for y in range(3):
+			cells[Vector2(x, y)] = 0
+	var tile_map_2d: DijkstraTileMap2D = add_tile_map_2d(cells)
+	var field: Dictionary = tile_map_2d.get_obstacle_distance_field()
+	assert_eq(field[Vector2(0, 0)], 0.0, "the edge of the map touches empty cells")
+	assert_eq(field[Vector2(2, 1)], 1.0, "one step from the edge")
+	tile_map_2d.disable_cell(Vector2(2, 0))
+	field = tile_map_2d.get_obstacle_distance_field()
+	assert_false(field.has(Vector2(2, 0)), "disabled cells are absent")
+	assert_eq(field[Vector2(2, 1)], 0.0, "next to the disabled cell")
+	tile_map_2d.get_parent().free()
+	return true
+
+
+func test_tile_map_get_costs_for_cells() -> bool:
+	var tile_map_2d: DijkstraTileMap2D = add_tile_map_2d(
+		{Vector2(0, 0): 0, Vector2(1, 0): 0, Vector2(2, 0): 0}
+	)
+	tile_map_2d.recalculate(tile_map_2d.get_id_for_cell(Vector2(0, 0)))
+	var cells: PoolVector2Array = PoolVector2Array([Vector2(2, 0), Vector2(0, 0), Vector2(9, 9)])
+	assert_eq(Array(tile_map_2d.get_costs_for_cells(cells)), [2.0, 0.0, INF])
+	tile_map_2d.get_parent().free()
+	return true